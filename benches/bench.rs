@@ -48,6 +48,73 @@ impl Config for FxConfig {
     }
 }
 
+/// Same layout as `FxConfig`, but with Fibonacci index mixing turned on, so the two can be
+/// benchmarked head-to-head over the same key distributions.
+struct FibConfig;
+
+impl Config for FibConfig {
+    type Key = TestKey;
+    type Value = u32;
+
+    type RawKey = [u8; 16];
+    type RawValue = [u8; 4];
+
+    type H = FxHashFn;
+
+    const USE_FIBONACCI_HASHING: bool = true;
+
+    #[inline]
+    fn encode_key(k: &Self::Key) -> Self::RawKey {
+        FxConfig::encode_key(k)
+    }
+
+    #[inline]
+    fn encode_value(v: &Self::Value) -> Self::RawValue {
+        FxConfig::encode_value(v)
+    }
+
+    #[inline]
+    fn decode_key(_k: &Self::RawKey) -> Self::Key {
+        panic!()
+    }
+
+    #[inline]
+    fn decode_value(v: &Self::RawValue) -> Self::Value {
+        FxConfig::decode_value(v)
+    }
+}
+
+/// Keys that only vary in their low bits (e.g. small sequential counters packed into a
+/// wide key). Masking a hash's low bits to compute a slot index collapses these onto a
+/// handful of probe chains; Fibonacci mixing should not.
+///
+/// The second field is held constant rather than randomized: a random field anywhere in
+/// the key would inject 64 bits of fresh entropy into `FxHashFn`'s output regardless of how
+/// skewed the first field is, masking the very clustering this distribution exists to show.
+fn generate_low_bit_heavy_test_data(num_values: usize) -> Vec<(TestKey, u32)> {
+    use rand::prelude::*;
+
+    (0..num_values)
+        .map(|i| (TestKey(i as u64 & 0xFFFF, 0), random()))
+        .collect()
+}
+
+/// Keys that only vary in their high bits (e.g. pointer- or timestamp-derived keys with a
+/// constant low-bit suffix). See `generate_low_bit_heavy_test_data` for why the second
+/// field is held constant instead of randomized.
+fn generate_high_bit_heavy_test_data(num_values: usize) -> Vec<(TestKey, u32)> {
+    use rand::prelude::*;
+
+    (0..num_values)
+        .map(|i| (TestKey((i as u64) << 48, 0), random()))
+        .collect()
+}
+
+/// Keys with no particular structure, used as the baseline for comparison.
+fn generate_random_test_data(num_values: usize) -> Vec<(TestKey, u32)> {
+    generate_test_data(num_values)
+}
+
 fn index_contained(i: usize) -> bool {
     i % 10 != 3
 }
@@ -143,6 +210,84 @@ fn bench_std_fx_lookup(b: &mut test::Bencher, num_values: usize) {
     })
 }
 
+fn generate_hash_table_for<C: Config<Key = TestKey, Value = u32>>(
+    test_data: &[(TestKey, u32)],
+    load_factor: f32,
+) -> HashTableBuilder<C> {
+    let mut table = HashTableBuilder::with_capacity(test_data.len(), load_factor);
+
+    for (key, value) in test_data {
+        table.insert(key, value);
+    }
+
+    table
+}
+
+/// Compares masked vs. Fibonacci-mixed indexing under a given key distribution: both
+/// configs see keys from the same generator (the exact same keys for the low- and
+/// high-bit-heavy distributions, a fresh random draw per bench for `random_keys`), so any
+/// difference in lookup throughput comes from how badly the distribution clusters under
+/// each indexing scheme.
+fn bench_clustering_lookup<C: Config<Key = TestKey, Value = u32>>(
+    b: &mut test::Bencher,
+    test_data: &[(TestKey, u32)],
+    load_factor: f32,
+) {
+    let table = generate_hash_table_for::<C>(test_data, load_factor);
+
+    let mut serialized = {
+        let mut data = Cursor::new(Vec::new());
+        table.serialize(&mut data).unwrap();
+        data.into_inner()
+    };
+
+    serialized.insert(0, 0xFFu8);
+
+    let table = HashTable::<C>::from_serialized(&serialized[1..]).unwrap();
+
+    b.iter(|| {
+        for _ in 0..100 {
+            for (key, value) in test_data {
+                assert!(table.get(key) == Some(*value));
+            }
+        }
+    })
+}
+
+macro_rules! bench_distribution {
+    ($name:ident, $generator:ident) => {
+        mod $name {
+            fn test_data() -> Vec<(crate::TestKey, u32)> {
+                crate::$generator(5000)
+            }
+
+            #[bench]
+            fn masked_load_50(b: &mut test::Bencher) {
+                crate::bench_clustering_lookup::<crate::FxConfig>(b, &test_data(), 0.5);
+            }
+
+            #[bench]
+            fn fibonacci_load_50(b: &mut test::Bencher) {
+                crate::bench_clustering_lookup::<crate::FibConfig>(b, &test_data(), 0.5);
+            }
+
+            #[bench]
+            fn masked_load_90(b: &mut test::Bencher) {
+                crate::bench_clustering_lookup::<crate::FxConfig>(b, &test_data(), 0.9);
+            }
+
+            #[bench]
+            fn fibonacci_load_90(b: &mut test::Bencher) {
+                crate::bench_clustering_lookup::<crate::FibConfig>(b, &test_data(), 0.9);
+            }
+        }
+    };
+}
+
+bench_distribution!(low_bit_heavy, generate_low_bit_heavy_test_data);
+bench_distribution!(high_bit_heavy, generate_high_bit_heavy_test_data);
+bench_distribution!(random_keys, generate_random_test_data);
+
 macro_rules! bench {
     ($name:ident, $num_values:expr) => {
         mod $name {
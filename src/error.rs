@@ -0,0 +1,109 @@
+use std::fmt;
+
+/// Errors that can occur while loading a serialized [`HashTable`](crate::HashTable).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The byte slice failed one or more of the structural checks performed by
+    /// [`HashTable::try_from_serialized`](crate::HashTable::try_from_serialized).
+    Verify(VerifyError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Verify(e) => write!(f, "invalid odht table: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<VerifyError> for Error {
+    fn from(e: VerifyError) -> Self {
+        Error::Verify(e)
+    }
+}
+
+/// The specific structural invariant that failed while verifying a serialized table.
+///
+/// This is deliberately granular: callers rejecting untrusted input (files, network
+/// payloads, ...) want to know *why* a buffer was rejected, not just that it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The buffer is too small to even contain a header.
+    BufferTooShort { expected_at_least: usize, actual: usize },
+
+    /// The header's magic bytes don't match `b"ODHT"`.
+    InvalidMagic,
+
+    /// The header declares a format version this build of odht doesn't know how to read.
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    /// `slot_count` is zero or not a power of two, which the probing scheme requires.
+    SlotCountNotPowerOfTwo { slot_count: u64 },
+
+    /// `item_count` is larger than `slot_count`, which can never happen in a table that
+    /// was built correctly.
+    ItemCountExceedsSlotCount { item_count: u64, slot_count: u64 },
+
+    /// The stored load factor (parts per mille) is outside of `(0, 1000]`.
+    LoadFactorOutOfRange { permille: u32 },
+
+    /// The buffer's length doesn't match `header_size + slot_count * slot_size` for the
+    /// `Config` being used to read it.
+    LengthMismatch { expected: usize, actual: usize },
+
+    /// A slot's control byte is neither "empty" nor "occupied". Reading past this point
+    /// would mean trusting a key/value encoding we can't distinguish from garbage.
+    InvalidControlByte { slot_index: u64, value: u8 },
+
+    /// `slot_count` passed the power-of-two check, but the buffer length implied by
+    /// `header_size + slot_count * slot_size` overflows `usize`. No real table built by
+    /// [`HashTableBuilder`](crate::HashTableBuilder) can reach this size; seeing it means
+    /// `slot_count` came from an untrusted or corrupted header.
+    SlotCountTooLarge { slot_count: u64 },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VerifyError::BufferTooShort { expected_at_least, actual } => write!(
+                f,
+                "buffer too short to contain a header: expected at least {} bytes, found {}",
+                expected_at_least, actual
+            ),
+            VerifyError::InvalidMagic => write!(f, "magic bytes do not match odht's header"),
+            VerifyError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported format version {} (this build supports version {})",
+                found, supported
+            ),
+            VerifyError::SlotCountNotPowerOfTwo { slot_count } => {
+                write!(f, "slot_count {} is not a power of two", slot_count)
+            }
+            VerifyError::ItemCountExceedsSlotCount { item_count, slot_count } => write!(
+                f,
+                "item_count {} exceeds slot_count {}",
+                item_count, slot_count
+            ),
+            VerifyError::LoadFactorOutOfRange { permille } => {
+                write!(f, "load factor {}‰ is out of the valid (0, 1000] range", permille)
+            }
+            VerifyError::LengthMismatch { expected, actual } => write!(
+                f,
+                "buffer length {} does not match the {} bytes implied by the header",
+                actual, expected
+            ),
+            VerifyError::InvalidControlByte { slot_index, value } => write!(
+                f,
+                "slot {} has control byte {}, which is neither empty (0) nor occupied (1)",
+                slot_index, value
+            ),
+            VerifyError::SlotCountTooLarge { slot_count } => write!(
+                f,
+                "slot_count {} is too large: the implied buffer length overflows usize",
+                slot_count
+            ),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+/// A hash function usable with a [`Config`](crate::Config).
+///
+/// Implementations don't need to be cryptographically strong, but `hash` does need to be
+/// deterministic across processes and platforms for a given `seed`: it determines which
+/// slot a key ends up in both when a table is built and whenever it is reopened later.
+pub trait HashFn: Eq {
+    /// The seed a newly built table should persist in its header and feed back into every
+    /// later call to `hash` for that table. Hash functions that don't need one (like
+    /// `FxHashFn`) can leave this as all zeroes; keyed hash functions (like `SipHashFn`)
+    /// return a fresh random key here so two tables aren't hashed the same way.
+    fn seed() -> [u8; 16] {
+        [0; 16]
+    }
+
+    fn hash(bytes: &[u8], seed: [u8; 16]) -> u64;
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// A fast, non-cryptographic hash function (a reimplementation of rustc's `FxHash`).
+///
+/// `FxHashFn` is predictable: anyone who knows a key can compute exactly which slot it
+/// lands in. That's fine when keys come from a trusted source, but makes tables built over
+/// attacker-controlled keys vulnerable to collision flooding. Use
+/// [`SipHashFn`](crate::SipHashFn) instead when that matters.
+#[derive(Eq, PartialEq)]
+pub struct FxHashFn;
+
+impl HashFn for FxHashFn {
+    #[inline]
+    fn hash(mut bytes: &[u8], _seed: [u8; 16]) -> u64 {
+        let mut hash = 0u64;
+
+        while bytes.len() >= 8 {
+            let mut chunk = [0u8; 8];
+            chunk.copy_from_slice(&bytes[..8]);
+            hash = round(hash, u64::from_le_bytes(chunk));
+            bytes = &bytes[8..];
+        }
+
+        if bytes.len() >= 4 {
+            let mut chunk = [0u8; 4];
+            chunk.copy_from_slice(&bytes[..4]);
+            hash = round(hash, u32::from_le_bytes(chunk) as u64);
+            bytes = &bytes[4..];
+        }
+
+        for &byte in bytes {
+            hash = round(hash, byte as u64);
+        }
+
+        hash
+    }
+}
+
+#[inline]
+fn round(hash: u64, value: u64) -> u64 {
+    (hash.rotate_left(5) ^ value).wrapping_mul(SEED)
+}
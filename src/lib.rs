@@ -0,0 +1,51 @@
+//! `odht` is an off-heap hash table that can be serialized to and deserialized from a flat
+//! byte buffer with no parsing step: the on-disk representation *is* the in-memory
+//! representation, so a table can be read directly out of a memory-mapped file.
+//!
+//! A table's layout is described by a [`Config`], which fixes the key/value types, their
+//! fixed-size raw encodings, and the [`HashFn`] used to place them.
+//!
+//! The `rayon` feature adds `HashTableBuilder::par_extend`/`from_par_iter` for building
+//! large tables on multiple threads, and `HashTable::par_iter` for scanning one in
+//! parallel.
+
+mod error;
+mod fxhash;
+mod raw_table;
+mod siphash;
+
+pub use error::{Error, VerifyError};
+pub use fxhash::{FxHashFn, HashFn};
+pub use raw_table::{HashTable, HashTableBuilder, HashTableMut, ValueMut};
+pub use siphash::SipHashFn;
+
+/// Describes how a table's keys and values are encoded, and which [`HashFn`] is used to
+/// place them.
+///
+/// `Key`/`Value` are the types applications work with; `RawKey`/`RawValue` are their
+/// fixed-size on-disk encodings. Keeping the two separate lets a table store, for example,
+/// `Key = String` as a fixed-size hash or interned id, while still exposing the original
+/// `String` through `get`.
+pub trait Config {
+    type Key;
+    type Value;
+
+    type RawKey: Copy + Eq;
+    type RawValue: Copy;
+
+    type H: HashFn;
+
+    /// When `true`, slot indices are derived by Fibonacci (multiply-shift) mixing the hash
+    /// instead of masking off its low bits. Turn this on for key distributions that vary
+    /// mostly in their low or high bits (sequential ids, pointer-derived keys, ...), where
+    /// plain masking makes unrelated keys collapse onto the same probe chain. Existing
+    /// configs default to `false`, so on-disk layouts are unaffected unless a `Config`
+    /// opts in.
+    const USE_FIBONACCI_HASHING: bool = false;
+
+    fn encode_key(k: &Self::Key) -> Self::RawKey;
+    fn encode_value(v: &Self::Value) -> Self::RawValue;
+
+    fn decode_key(k: &Self::RawKey) -> Self::Key;
+    fn decode_value(v: &Self::RawValue) -> Self::Value;
+}
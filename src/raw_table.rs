@@ -0,0 +1,825 @@
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+use crate::error::VerifyError;
+use crate::{Config, HashFn};
+
+const MAGIC: [u8; 4] = *b"ODHT";
+const FORMAT_VERSION: u32 = 2;
+const HEADER_SIZE: usize = 44;
+
+const EMPTY: u8 = 0;
+const OCCUPIED: u8 = 1;
+
+/// The fixed-size prefix of a serialized table.
+///
+/// Every field is read and written through explicit little-endian byte conversions rather
+/// than by overlaying a struct on the buffer, since the buffer handed to
+/// [`HashTable::from_serialized`] is not guaranteed to be aligned (e.g. it may be a
+/// memory-mapped file opened at an arbitrary offset).
+#[derive(Clone, Copy)]
+struct Header {
+    item_count: u64,
+    slot_count: u64,
+    max_load_factor_permille: u32,
+    /// The seed `Config::H` was keyed with when this table was built, fed back into every
+    /// `hash` call so a reopened table places lookups in the same slots it was built with.
+    /// Unused (all zero) for hash functions that don't key themselves, like `FxHashFn`.
+    hash_seed: [u8; 16],
+}
+
+impl Header {
+    fn write_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&self.item_count.to_le_bytes());
+        out.extend_from_slice(&self.slot_count.to_le_bytes());
+        out.extend_from_slice(&self.max_load_factor_permille.to_le_bytes());
+        out.extend_from_slice(&self.hash_seed);
+    }
+
+    /// Parses and validates the header, returning an error for every way the bytes could
+    /// fail to describe a well-formed table instead of panicking or reading out of bounds.
+    fn verify<C: Config>(data: &[u8]) -> Result<Header, VerifyError> {
+        if data.len() < HEADER_SIZE {
+            return Err(VerifyError::BufferTooShort {
+                expected_at_least: HEADER_SIZE,
+                actual: data.len(),
+            });
+        }
+
+        if data[0..4] != MAGIC[..] {
+            return Err(VerifyError::InvalidMagic);
+        }
+
+        let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+        if version != FORMAT_VERSION {
+            return Err(VerifyError::UnsupportedVersion {
+                found: version,
+                supported: FORMAT_VERSION,
+            });
+        }
+
+        let item_count = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let slot_count = u64::from_le_bytes(data[16..24].try_into().unwrap());
+        let max_load_factor_permille = u32::from_le_bytes(data[24..28].try_into().unwrap());
+        let mut hash_seed = [0u8; 16];
+        hash_seed.copy_from_slice(&data[28..44]);
+
+        if slot_count == 0 || !slot_count.is_power_of_two() {
+            return Err(VerifyError::SlotCountNotPowerOfTwo { slot_count });
+        }
+
+        if item_count > slot_count {
+            return Err(VerifyError::ItemCountExceedsSlotCount { item_count, slot_count });
+        }
+
+        if max_load_factor_permille == 0 || max_load_factor_permille > 1000 {
+            return Err(VerifyError::LoadFactorOutOfRange { permille: max_load_factor_permille });
+        }
+
+        // `slot_count` is attacker-controlled input at this point: it has only been checked
+        // to be a power of two, so a value like `2^62` must not be allowed to overflow this
+        // multiplication/addition and panic -- it has to fail verification instead.
+        let slots_len = (slot_count as usize)
+            .checked_mul(slot_size::<C>())
+            .ok_or(VerifyError::SlotCountTooLarge { slot_count })?;
+        let expected_len = HEADER_SIZE
+            .checked_add(slots_len)
+            .ok_or(VerifyError::SlotCountTooLarge { slot_count })?;
+        if data.len() != expected_len {
+            return Err(VerifyError::LengthMismatch { expected: expected_len, actual: data.len() });
+        }
+
+        // Every slot's control byte must be EMPTY or OCCUPIED before we let typed reads
+        // (`read_key`/`read_value`) trust the bytes that follow it.
+        for slot_index in 0..slot_count {
+            let control_offset = slot_offset::<C>(slot_index as usize);
+            let control_byte = data[control_offset];
+            if control_byte != EMPTY && control_byte != OCCUPIED {
+                return Err(VerifyError::InvalidControlByte { slot_index, value: control_byte });
+            }
+        }
+
+        Ok(Header { item_count, slot_count, max_load_factor_permille, hash_seed })
+    }
+}
+
+#[inline]
+fn slot_size<C: Config>() -> usize {
+    1 + size_of::<C::RawKey>() + size_of::<C::RawValue>()
+}
+
+#[inline]
+fn slot_offset<C: Config>(slot_index: usize) -> usize {
+    HEADER_SIZE + slot_index * slot_size::<C>()
+}
+
+/// Like [`slot_offset`], but for a buffer that holds only the slot array with no header
+/// prefix -- i.e. [`HashTableBuilder::slots`], which only gains its header when
+/// [`HashTableBuilder::serialize`] writes it out.
+#[inline]
+fn slot_body_offset<C: Config>(slot_index: usize) -> usize {
+    slot_index * slot_size::<C>()
+}
+
+/// The constant used for Fibonacci (multiply-shift) hashing: the closest odd integer to
+/// `2^64 / golden_ratio`. Multiplying by it spreads the entropy of every input bit across
+/// the high bits of the product, so that masking off the low bits (as plain `hash &
+/// (slot_count - 1)` does) doesn't collapse keys that only differ in a handful of bits.
+const FIBONACCI_MULTIPLIER: u64 = 0x9E37_79B9_7F4A_7C15;
+
+#[inline]
+fn start_index<C: Config>(hash: u64, slot_count: u64) -> usize {
+    if C::USE_FIBONACCI_HASHING {
+        // A one-slot table has no bits left to shift into, and `64 - 0` would overflow the
+        // shift below anyway -- there's only one possible index.
+        if slot_count == 1 {
+            return 0;
+        }
+
+        let shift = 64 - slot_count.trailing_zeros();
+        (hash.wrapping_mul(FIBONACCI_MULTIPLIER) >> shift) as usize
+    } else {
+        (hash & (slot_count - 1)) as usize
+    }
+}
+
+/// Reads the raw key stored at `slot_index`, via an unaligned read since the backing
+/// buffer may not satisfy `RawKey`'s natural alignment.
+#[inline]
+unsafe fn read_key<C: Config>(data: &[u8], slot_index: usize) -> C::RawKey {
+    let offset = slot_offset::<C>(slot_index) + 1;
+    (data.as_ptr().add(offset) as *const C::RawKey).read_unaligned()
+}
+
+#[inline]
+unsafe fn read_value<C: Config>(data: &[u8], slot_index: usize) -> C::RawValue {
+    let offset = slot_offset::<C>(slot_index) + 1 + size_of::<C::RawKey>();
+    (data.as_ptr().add(offset) as *const C::RawValue).read_unaligned()
+}
+
+/// Probes `data` for `raw_key`, returning its slot index if present. Shared by every
+/// read path (`HashTable::get`, `HashTableMut::get`/`get_mut`/`set_value`) since they all
+/// need to locate an existing key the same way.
+fn find_slot<C: Config>(
+    data: &[u8],
+    slot_count: u64,
+    seed: [u8; 16],
+    raw_key: C::RawKey,
+) -> Option<usize> {
+    let hash = C::H::hash(as_bytes(&raw_key), seed);
+    let mut index = start_index::<C>(hash, slot_count);
+
+    for _ in 0..slot_count {
+        let control_offset = slot_offset::<C>(index);
+        match data[control_offset] {
+            EMPTY => return None,
+            OCCUPIED => {
+                if unsafe { read_key::<C>(data, index) } == raw_key {
+                    return Some(index);
+                }
+            }
+            _ => unreachable!("control byte was validated at load time"),
+        }
+
+        index = (index + 1) % slot_count as usize;
+    }
+
+    None
+}
+
+#[inline]
+unsafe fn write_value<C: Config>(data: &mut [u8], slot_index: usize, value: C::RawValue) {
+    let offset = slot_offset::<C>(slot_index) + 1 + size_of::<C::RawKey>();
+    (data.as_mut_ptr().add(offset) as *mut C::RawValue).write_unaligned(value);
+}
+
+/// Probes `region` starting at `start` (wrapping within `region`'s own `region_slot_count`
+/// slots) and writes `raw_key`/`raw_value` into the first empty slot found. Shared by the
+/// serial [`HashTableBuilder::insert`] and [`HashTableBuilder::par_extend`], both of which
+/// operate on [`HashTableBuilder::slots`] directly -- a buffer that holds only the slot
+/// array, with no header prefix, since the header is only prepended once by
+/// [`HashTableBuilder::serialize`]. Offsets within `region` are therefore computed with
+/// [`slot_body_offset`], not [`slot_offset`].
+fn insert_raw<C: Config>(
+    region: &mut [u8],
+    region_slot_count: u64,
+    start: usize,
+    raw_key: C::RawKey,
+    raw_value: C::RawValue,
+) {
+    let mut index = start;
+
+    loop {
+        let control_offset = slot_body_offset::<C>(index);
+        if region[control_offset] == EMPTY {
+            region[control_offset] = OCCUPIED;
+
+            let key_offset = control_offset + 1;
+            region[key_offset..key_offset + size_of::<C::RawKey>()]
+                .copy_from_slice(as_bytes(&raw_key));
+
+            let value_offset = key_offset + size_of::<C::RawKey>();
+            region[value_offset..value_offset + size_of::<C::RawValue>()]
+                .copy_from_slice(as_bytes(&raw_value));
+
+            return;
+        }
+
+        index = (index + 1) % region_slot_count as usize;
+    }
+}
+
+/// A read-only view over a serialized odht table.
+///
+/// `HashTable` borrows its backing bytes rather than owning them, so it can be built
+/// directly over a memory-mapped file without copying the data into the process.
+pub struct HashTable<'a, C: Config> {
+    data: &'a [u8],
+    header: Header,
+    _config: PhantomData<C>,
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, C: Config> HashTable<'a, C>
+where
+    C: Sync,
+    C::Key: Send,
+    C::Value: Send,
+{
+    /// A parallel version of iterating over every entry in the table.
+    ///
+    /// The on-disk format is a flat slot array, so unlike writes, reads need no
+    /// synchronization: this simply splits the slot range into disjoint chunks that rayon
+    /// hands out to worker threads, each scanning its chunk independently.
+    pub fn par_iter(&self) -> impl rayon::iter::ParallelIterator<Item = (C::Key, C::Value)> + '_ {
+        use rayon::prelude::*;
+
+        let slot_count = self.header.slot_count as usize;
+
+        (0..slot_count).into_par_iter().filter_map(move |index| {
+            let control_offset = slot_offset::<C>(index);
+            if self.data[control_offset] == OCCUPIED {
+                let raw_key = unsafe { read_key::<C>(self.data, index) };
+                let raw_value = unsafe { read_value::<C>(self.data, index) };
+                Some((C::decode_key(&raw_key), C::decode_value(&raw_value)))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<'a, C: Config> HashTable<'a, C> {
+    /// Validates every structural invariant of `data` before treating it as a table,
+    /// returning a [`VerifyError`] describing the first check that failed instead of
+    /// panicking or invoking undefined behavior on malformed input.
+    ///
+    /// This is the constructor to use whenever `data` did not come from
+    /// [`HashTableBuilder::serialize`] called by code you trust -- e.g. a file loaded from
+    /// disk or received over the network.
+    pub fn try_from_serialized(data: &'a [u8]) -> Result<Self, VerifyError> {
+        let header = Header::verify::<C>(data)?;
+        Ok(HashTable { data, header, _config: PhantomData })
+    }
+
+    /// Like [`Self::try_from_serialized`], but reports failures as [`crate::Error`].
+    pub fn from_serialized(data: &'a [u8]) -> Result<Self, crate::Error> {
+        Ok(Self::try_from_serialized(data)?)
+    }
+
+    pub fn len(&self) -> usize {
+        self.header.item_count as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.header.item_count == 0
+    }
+
+    pub fn get(&self, key: &C::Key) -> Option<C::Value> {
+        let raw_key = C::encode_key(key);
+        let index = find_slot::<C>(self.data, self.header.slot_count, self.header.hash_seed, raw_key)?;
+        let raw_value = unsafe { read_value::<C>(self.data, index) };
+        Some(C::decode_value(&raw_value))
+    }
+}
+
+/// Builds a table in memory, then serializes it to the on-disk slot-array format read by
+/// [`HashTable`].
+pub struct HashTableBuilder<C: Config> {
+    slots: Vec<u8>,
+    slot_count: u64,
+    item_count: u64,
+    max_load_factor_permille: u32,
+    hash_seed: [u8; 16],
+    _config: PhantomData<C>,
+}
+
+#[cfg(feature = "rayon")]
+impl<C: Config> HashTableBuilder<C>
+where
+    C: Sync,
+    C::Key: Send,
+    C::Value: Send,
+    C::RawKey: Send,
+    C::RawValue: Send,
+{
+    /// Hashes and encodes every item produced by `iter` in parallel, then inserts them.
+    ///
+    /// Hashing and encoding are the expensive, embarrassingly parallel part of a bulk load,
+    /// so that work runs across threads. Placing the hashed entries into the slot array,
+    /// however, has to run single-threaded: the serial probe sequence used by
+    /// [`Self::insert`] can wrap from the end of the array back to its start, so two
+    /// entries whose probe chains overlap must be placed in the same order `insert` would
+    /// use. Sharding that placement step across disjoint sub-ranges (as an earlier version
+    /// of this method did) breaks exactly that guarantee: a chain that fills one shard
+    /// wraps back to the shard's own start instead of spilling into the next shard, so
+    /// `HashTable::get` -- which always probes the whole array -- can't find the entry.
+    /// As with the serial `insert`, the caller is responsible for not inserting the same
+    /// key twice and for not exceeding the capacity the table was created with.
+    pub fn par_extend<I>(&mut self, iter: I)
+    where
+        I: rayon::iter::IntoParallelIterator<Item = (C::Key, C::Value)>,
+    {
+        use rayon::prelude::*;
+
+        let slot_count = self.slot_count;
+        let seed = self.hash_seed;
+
+        let hashed: Vec<(usize, C::RawKey, C::RawValue)> = iter
+            .into_par_iter()
+            .map(|(key, value)| {
+                let raw_key = C::encode_key(&key);
+                let raw_value = C::encode_value(&value);
+                let hash = C::H::hash(as_bytes(&raw_key), seed);
+                let index = start_index::<C>(hash, slot_count);
+                (index, raw_key, raw_value)
+            })
+            .collect();
+
+        for (index, raw_key, raw_value) in hashed {
+            insert_raw::<C>(&mut self.slots, slot_count, index, raw_key, raw_value);
+            self.item_count += 1;
+        }
+    }
+
+    /// Builds a table from a parallel iterator in one call: allocates capacity for
+    /// `max_item_count` entries at `max_load_factor`, then runs [`Self::par_extend`] over
+    /// `iter`.
+    pub fn from_par_iter<I>(iter: I, max_item_count: usize, max_load_factor: f32) -> Self
+    where
+        I: rayon::iter::IntoParallelIterator<Item = (C::Key, C::Value)>,
+    {
+        let mut builder = HashTableBuilder::with_capacity(max_item_count, max_load_factor);
+        builder.par_extend(iter);
+        builder
+    }
+}
+
+impl<C: Config> HashTableBuilder<C> {
+    /// Allocates a table sized to hold up to `max_item_count` entries at `max_load_factor`
+    /// (e.g. `0.8`).
+    pub fn with_capacity(max_item_count: usize, max_load_factor: f32) -> Self {
+        assert!(
+            max_load_factor > 0.0 && max_load_factor <= 1.0,
+            "max_load_factor must be in (0.0, 1.0], got {}",
+            max_load_factor
+        );
+
+        let needed = if max_item_count == 0 {
+            1
+        } else {
+            (max_item_count as f32 / max_load_factor).ceil() as usize
+        };
+        let slot_count = needed.next_power_of_two().max(1) as u64;
+
+        HashTableBuilder {
+            slots: vec![EMPTY; slot_count as usize * slot_size::<C>()],
+            slot_count,
+            item_count: 0,
+            max_load_factor_permille: (max_load_factor * 1000.0).round() as u32,
+            hash_seed: C::H::seed(),
+            _config: PhantomData,
+        }
+    }
+
+    /// Inserts `key` -> `value`, assuming `key` is not already present. The caller is
+    /// responsible for only inserting each key once.
+    pub fn insert(&mut self, key: &C::Key, value: &C::Value) {
+        let raw_key = C::encode_key(key);
+        let raw_value = C::encode_value(value);
+        let hash = C::H::hash(as_bytes(&raw_key), self.hash_seed);
+
+        let index = start_index::<C>(hash, self.slot_count);
+        insert_raw::<C>(&mut self.slots, self.slot_count, index, raw_key, raw_value);
+
+        self.item_count += 1;
+    }
+
+    /// Writes this table out in the format read by [`HashTable::from_serialized`].
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let header = Header {
+            item_count: self.item_count,
+            slot_count: self.slot_count,
+            max_load_factor_permille: self.max_load_factor_permille,
+            hash_seed: self.hash_seed,
+        };
+
+        let mut out = Vec::with_capacity(HEADER_SIZE + self.slots.len());
+        header.write_to(&mut out);
+        out.extend_from_slice(&self.slots);
+
+        writer.write_all(&out)
+    }
+}
+
+#[inline]
+fn as_bytes<T: Copy>(value: &T) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) }
+}
+
+/// A mutable view over a serialized odht table, for updating values in place.
+///
+/// Unlike [`HashTable`], `HashTableMut` does not support inserting new keys -- doing so
+/// could require growing the slot array, which an in-place view over a fixed-size buffer
+/// (e.g. a memory-mapped file) cannot do. It can only overwrite the `RawValue` bytes of a
+/// key that is already present, leaving every key and control byte untouched. That makes
+/// it a good fit for counter- or cache-style tables where the key set is stable but values
+/// change often.
+pub struct HashTableMut<'a, C: Config> {
+    data: &'a mut [u8],
+    header: Header,
+    _config: PhantomData<C>,
+}
+
+impl<'a, C: Config> HashTableMut<'a, C> {
+    /// Validates `data` the same way [`HashTable::try_from_serialized`] does, then returns
+    /// a view that can update values in place.
+    pub fn from_serialized_mut(data: &'a mut [u8]) -> Result<Self, crate::Error> {
+        let header = Header::verify::<C>(data)?;
+        Ok(HashTableMut { data, header, _config: PhantomData })
+    }
+
+    pub fn get(&self, key: &C::Key) -> Option<C::Value> {
+        let raw_key = C::encode_key(key);
+        let index = find_slot::<C>(self.data, self.header.slot_count, self.header.hash_seed, raw_key)?;
+        let raw_value = unsafe { read_value::<C>(self.data, index) };
+        Some(C::decode_value(&raw_value))
+    }
+
+    /// Returns a handle for reading and overwriting `key`'s value in place, or `None` if
+    /// `key` is not present.
+    pub fn get_mut(&mut self, key: &C::Key) -> Option<ValueMut<'_, C>> {
+        let raw_key = C::encode_key(key);
+        let index = find_slot::<C>(self.data, self.header.slot_count, self.header.hash_seed, raw_key)?;
+        Some(ValueMut { data: self.data, index, _config: PhantomData })
+    }
+
+    /// Overwrites the value stored for `key`, returning whether `key` was present. Does
+    /// nothing if `key` is absent -- this never inserts.
+    pub fn set_value(&mut self, key: &C::Key, value: &C::Value) -> bool {
+        match self.get_mut(key) {
+            Some(mut slot) => {
+                slot.set(value);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A handle onto a single value slot of a [`HashTableMut`], returned by
+/// [`HashTableMut::get_mut`].
+pub struct ValueMut<'a, C: Config> {
+    data: &'a mut [u8],
+    index: usize,
+    _config: PhantomData<C>,
+}
+
+impl<'a, C: Config> ValueMut<'a, C> {
+    pub fn get(&self) -> C::Value {
+        let raw_value = unsafe { read_value::<C>(self.data, self.index) };
+        C::decode_value(&raw_value)
+    }
+
+    pub fn set(&mut self, value: &C::Value) {
+        let raw_value = C::encode_value(value);
+        unsafe { write_value::<C>(self.data, self.index, raw_value) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FxHashFn;
+
+    pub(super) struct TestConfig;
+
+    impl Config for TestConfig {
+        type Key = u64;
+        type Value = u32;
+
+        type RawKey = u64;
+        type RawValue = u32;
+
+        type H = FxHashFn;
+
+        fn encode_key(k: &u64) -> u64 {
+            *k
+        }
+
+        fn encode_value(v: &u32) -> u32 {
+            *v
+        }
+
+        fn decode_key(k: &u64) -> u64 {
+            *k
+        }
+
+        fn decode_value(v: &u32) -> u32 {
+            *v
+        }
+    }
+
+    fn build_table(entries: &[(u64, u32)]) -> Vec<u8> {
+        let mut builder = HashTableBuilder::<TestConfig>::with_capacity(entries.len(), 0.8);
+        for (k, v) in entries {
+            builder.insert(k, v);
+        }
+
+        let mut out = Vec::new();
+        builder.serialize(&mut out).unwrap();
+        out
+    }
+
+    /// `HashTable` deliberately has no `Debug`/`PartialEq` impl, so assert against the
+    /// `VerifyError` directly rather than against the whole `Result`.
+    fn verify_err(data: &[u8]) -> VerifyError {
+        match HashTable::<TestConfig>::try_from_serialized(data) {
+            Ok(_) => panic!("expected verification to fail"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn roundtrip_build_serialize_get() {
+        let entries: Vec<(u64, u32)> = (0..200).map(|i| (i as u64, (i * 7) as u32)).collect();
+        let serialized = build_table(&entries);
+
+        let table = HashTable::<TestConfig>::from_serialized(&serialized).unwrap();
+        for (k, v) in &entries {
+            assert_eq!(table.get(k), Some(*v));
+        }
+        assert_eq!(table.get(&999_999), None);
+        assert_eq!(table.len(), entries.len());
+    }
+
+    #[test]
+    fn roundtrip_single_slot_table() {
+        // Regression test for the offset bug: a header-less `slots` buffer indexed with a
+        // header-inclusive offset panics (or silently misplaces entries) on the very first
+        // insert for a table this small.
+        // `build_table` uses a 0.8 load factor, which rounds one entry up to two slots; a
+        // 1.0 load factor is what gives a single slot.
+        let mut builder = HashTableBuilder::<TestConfig>::with_capacity(1, 1.0);
+        builder.insert(&42, &1);
+        let mut serialized = Vec::new();
+        builder.serialize(&mut serialized).unwrap();
+        let table = HashTable::<TestConfig>::from_serialized(&serialized).unwrap();
+        assert_eq!(table.get(&42), Some(1));
+        assert_eq!(table.get(&43), None);
+    }
+
+    #[test]
+    fn verify_rejects_buffer_too_short() {
+        assert_eq!(
+            verify_err(&[0u8; 4]),
+            VerifyError::BufferTooShort { expected_at_least: HEADER_SIZE, actual: 4 }
+        );
+    }
+
+    #[test]
+    fn verify_rejects_invalid_magic() {
+        let mut serialized = build_table(&[(1, 1)]);
+        serialized[0] = b'X';
+        assert_eq!(verify_err(&serialized), VerifyError::InvalidMagic);
+    }
+
+    #[test]
+    fn verify_rejects_unsupported_version() {
+        let mut serialized = build_table(&[(1, 1)]);
+        serialized[4..8].copy_from_slice(&99u32.to_le_bytes());
+        assert_eq!(
+            verify_err(&serialized),
+            VerifyError::UnsupportedVersion { found: 99, supported: FORMAT_VERSION }
+        );
+    }
+
+    #[test]
+    fn verify_rejects_non_power_of_two_slot_count() {
+        let mut serialized = build_table(&[(1, 1)]);
+        serialized[16..24].copy_from_slice(&3u64.to_le_bytes());
+        assert_eq!(
+            verify_err(&serialized),
+            VerifyError::SlotCountNotPowerOfTwo { slot_count: 3 }
+        );
+    }
+
+    #[test]
+    fn verify_rejects_item_count_exceeding_slot_count() {
+        let mut serialized = build_table(&[(1, 1)]);
+        let slot_count = u64::from_le_bytes(serialized[16..24].try_into().unwrap());
+        serialized[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            verify_err(&serialized),
+            VerifyError::ItemCountExceedsSlotCount { item_count: u64::MAX, slot_count }
+        );
+    }
+
+    #[test]
+    fn verify_rejects_load_factor_out_of_range() {
+        let mut serialized = build_table(&[(1, 1)]);
+        serialized[24..28].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(verify_err(&serialized), VerifyError::LoadFactorOutOfRange { permille: 0 });
+    }
+
+    #[test]
+    fn verify_rejects_length_mismatch() {
+        let mut serialized = build_table(&[(1, 1)]);
+        serialized.pop();
+        assert!(matches!(verify_err(&serialized), VerifyError::LengthMismatch { .. }));
+    }
+
+    #[test]
+    fn verify_rejects_invalid_control_byte() {
+        let mut serialized = build_table(&[(1, 1)]);
+        serialized[HEADER_SIZE] = 0xAB;
+        assert_eq!(
+            verify_err(&serialized),
+            VerifyError::InvalidControlByte { slot_index: 0, value: 0xAB }
+        );
+    }
+
+    #[test]
+    fn verify_rejects_slot_count_that_would_overflow_expected_length() {
+        let mut data = vec![0u8; HEADER_SIZE];
+        data[0..4].copy_from_slice(&MAGIC);
+        data[4..8].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+        data[8..16].copy_from_slice(&0u64.to_le_bytes());
+        let slot_count = 1u64 << 62;
+        data[16..24].copy_from_slice(&slot_count.to_le_bytes());
+        data[24..28].copy_from_slice(&500u32.to_le_bytes());
+
+        assert_eq!(verify_err(&data), VerifyError::SlotCountTooLarge { slot_count });
+    }
+}
+
+#[cfg(test)]
+mod mut_tests {
+    use super::tests::TestConfig;
+    use super::*;
+
+    fn build_table(entries: &[(u64, u32)]) -> Vec<u8> {
+        let mut builder = HashTableBuilder::<TestConfig>::with_capacity(entries.len(), 0.8);
+        for (k, v) in entries {
+            builder.insert(k, v);
+        }
+
+        let mut out = Vec::new();
+        builder.serialize(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn get_mut_updates_value_in_place() {
+        let mut serialized = build_table(&[(1, 10), (2, 20), (3, 30)]);
+
+        {
+            let mut table = HashTableMut::<TestConfig>::from_serialized_mut(&mut serialized).unwrap();
+            let mut slot = table.get_mut(&2).unwrap();
+            assert_eq!(slot.get(), 20);
+            slot.set(&99);
+        }
+
+        let table = HashTable::<TestConfig>::from_serialized(&serialized).unwrap();
+        assert_eq!(table.get(&1), Some(10));
+        assert_eq!(table.get(&2), Some(99));
+        assert_eq!(table.get(&3), Some(30));
+    }
+
+    #[test]
+    fn set_value_overwrites_an_existing_key_and_reports_presence() {
+        let mut serialized = build_table(&[(1, 10), (2, 20)]);
+        let mut table = HashTableMut::<TestConfig>::from_serialized_mut(&mut serialized).unwrap();
+
+        assert!(table.set_value(&1, &42));
+        assert_eq!(table.get(&1), Some(42));
+    }
+
+    #[test]
+    fn set_value_on_absent_key_does_not_insert_and_returns_false() {
+        let mut serialized = build_table(&[(1, 10), (2, 20)]);
+        let before = serialized.clone();
+
+        let mut table = HashTableMut::<TestConfig>::from_serialized_mut(&mut serialized).unwrap();
+        assert!(!table.set_value(&3, &42));
+        assert_eq!(table.get(&3), None);
+
+        // An absent key must leave every byte untouched -- `HashTableMut` can never grow the
+        // slot array, so there is nowhere for a new entry to go.
+        assert_eq!(serialized, before);
+    }
+
+    #[test]
+    fn get_mut_on_absent_key_returns_none() {
+        let mut serialized = build_table(&[(1, 10)]);
+        let mut table = HashTableMut::<TestConfig>::from_serialized_mut(&mut serialized).unwrap();
+        assert!(table.get_mut(&2).is_none());
+    }
+}
+
+#[cfg(all(test, feature = "rayon"))]
+mod rayon_tests {
+    use super::*;
+
+    /// Hashes every key to the same value, so that filling a table to capacity forces every
+    /// insertion after the first to collide and wrap past the end of the slot array -- the
+    /// scenario a sharded parallel build got wrong.
+    #[derive(Eq, PartialEq)]
+    struct ConstHashFn;
+
+    impl HashFn for ConstHashFn {
+        fn hash(_bytes: &[u8], _seed: [u8; 16]) -> u64 {
+            0
+        }
+    }
+
+    struct CollidingConfig;
+
+    impl Config for CollidingConfig {
+        type Key = u64;
+        type Value = u32;
+
+        type RawKey = u64;
+        type RawValue = u32;
+
+        type H = ConstHashFn;
+
+        fn encode_key(k: &u64) -> u64 {
+            *k
+        }
+
+        fn encode_value(v: &u32) -> u32 {
+            *v
+        }
+
+        fn decode_key(k: &u64) -> u64 {
+            *k
+        }
+
+        fn decode_value(v: &u32) -> u32 {
+            *v
+        }
+    }
+
+    #[test]
+    fn par_extend_reproduces_whole_table_probe_sequence() {
+        let n = 64;
+        let entries: Vec<(u64, u32)> = (0..n).map(|i| (i as u64, i as u32 * 3)).collect();
+
+        let mut builder = HashTableBuilder::<CollidingConfig>::with_capacity(n, 1.0);
+        builder.par_extend(entries.clone());
+
+        let mut serialized = Vec::new();
+        builder.serialize(&mut serialized).unwrap();
+        let table = HashTable::<CollidingConfig>::from_serialized(&serialized).unwrap();
+
+        for (k, v) in &entries {
+            assert_eq!(table.get(k), Some(*v));
+        }
+    }
+
+    #[test]
+    fn par_extend_matches_serial_insert() {
+        let n = 64;
+        let entries: Vec<(u64, u32)> = (0..n).map(|i| (i as u64, i as u32 * 3)).collect();
+
+        let mut par_builder = HashTableBuilder::<CollidingConfig>::with_capacity(n, 1.0);
+        par_builder.par_extend(entries.clone());
+        let mut par_serialized = Vec::new();
+        par_builder.serialize(&mut par_serialized).unwrap();
+
+        let mut serial_builder = HashTableBuilder::<CollidingConfig>::with_capacity(n, 1.0);
+        for (key, value) in &entries {
+            serial_builder.insert(key, value);
+        }
+        let mut serial_serialized = Vec::new();
+        serial_builder.serialize(&mut serial_serialized).unwrap();
+
+        // Parallel hashing/encoding followed by single-threaded placement must land every
+        // key in the exact same slot the fully serial build would have used.
+        assert_eq!(par_serialized, serial_serialized);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::HashFn;
+
+/// A keyed hash function (SipHash-1-3) suitable for tables built over untrusted,
+/// potentially attacker-controlled keys.
+///
+/// [`FxHashFn`](crate::FxHashFn) is fast but fully predictable: anyone who knows a key
+/// knows exactly which slot it lands in, so an attacker who controls the keys going into a
+/// table can always pick ones that collide, degrading every lookup to an O(n) scan (the
+/// classic "HashDoS"). `SipHashFn` is keyed with a random 128-bit seed chosen when the
+/// table is built and persisted in its header, so an attacker who doesn't know that seed
+/// cannot predict, and therefore cannot target, any particular probe chain. The tradeoff is
+/// speed: SipHash-1-3 does meaningfully more work per byte than `FxHashFn`, so prefer it
+/// only when keys may be adversarial.
+#[derive(Eq, PartialEq)]
+pub struct SipHashFn;
+
+impl HashFn for SipHashFn {
+    fn seed() -> [u8; 16] {
+        random_seed()
+    }
+
+    #[inline]
+    fn hash(bytes: &[u8], seed: [u8; 16]) -> u64 {
+        let k0 = u64::from_le_bytes(seed[0..8].try_into().unwrap());
+        let k1 = u64::from_le_bytes(seed[8..16].try_into().unwrap());
+        siphash13(k0, k1, bytes)
+    }
+}
+
+/// Draws a random seed from the same source `std::collections::HashMap` uses to randomize
+/// its own hasher, so we don't need to pull in a `rand`-style dependency just to seed one
+/// hash function.
+fn random_seed() -> [u8; 16] {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let a = RandomState::new().build_hasher().finish();
+    let b = RandomState::new().build_hasher().finish();
+
+    let mut seed = [0u8; 16];
+    seed[0..8].copy_from_slice(&a.to_le_bytes());
+    seed[8..16].copy_from_slice(&b.to_le_bytes());
+    seed
+}
+
+#[inline]
+fn siphash13(k0: u64, k1: u64, data: &[u8]) -> u64 {
+    let mut v0 = k0 ^ 0x736f_6d65_7073_6575;
+    let mut v1 = k1 ^ 0x646f_7261_6e64_6f6d;
+    let mut v2 = k0 ^ 0x6c79_6765_6e65_7261;
+    let mut v3 = k1 ^ 0x7465_6462_7974_6573;
+
+    let len = data.len();
+    let chunks = data.chunks_exact(8);
+    let tail = chunks.remainder();
+
+    for chunk in chunks {
+        let m = u64::from_le_bytes(chunk.try_into().unwrap());
+        v3 ^= m;
+        sipround(&mut v0, &mut v1, &mut v2, &mut v3);
+        v0 ^= m;
+    }
+
+    let mut last_block = [0u8; 8];
+    last_block[..tail.len()].copy_from_slice(tail);
+    last_block[7] = (len & 0xff) as u8;
+    let m = u64::from_le_bytes(last_block);
+
+    v3 ^= m;
+    sipround(&mut v0, &mut v1, &mut v2, &mut v3);
+    v0 ^= m;
+
+    v2 ^= 0xff;
+    sipround(&mut v0, &mut v1, &mut v2, &mut v3);
+    sipround(&mut v0, &mut v1, &mut v2, &mut v3);
+    sipround(&mut v0, &mut v1, &mut v2, &mut v3);
+
+    v0 ^ v1 ^ v2 ^ v3
+}
+
+#[inline]
+fn sipround(v0: &mut u64, v1: &mut u64, v2: &mut u64, v3: &mut u64) {
+    *v0 = v0.wrapping_add(*v1);
+    *v1 = v1.rotate_left(13);
+    *v1 ^= *v0;
+    *v0 = v0.rotate_left(32);
+
+    *v2 = v2.wrapping_add(*v3);
+    *v3 = v3.rotate_left(16);
+    *v3 ^= *v2;
+
+    *v0 = v0.wrapping_add(*v3);
+    *v3 = v3.rotate_left(21);
+    *v3 ^= *v0;
+
+    *v2 = v2.wrapping_add(*v1);
+    *v1 = v1.rotate_left(17);
+    *v1 ^= *v2;
+    *v2 = v2.rotate_left(32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_deterministic_for_a_given_seed() {
+        let seed = [7u8; 16];
+        let data = b"the quick brown fox";
+        assert_eq!(SipHashFn::hash(data, seed), SipHashFn::hash(data, seed));
+    }
+
+    #[test]
+    fn hash_differs_across_seeds() {
+        let data = b"the quick brown fox";
+        assert_ne!(SipHashFn::hash(data, [0u8; 16]), SipHashFn::hash(data, [1u8; 16]));
+    }
+
+    #[test]
+    fn hash_differs_across_inputs() {
+        let seed = [3u8; 16];
+        assert_ne!(SipHashFn::hash(b"abc", seed), SipHashFn::hash(b"abd", seed));
+    }
+
+    #[test]
+    fn hash_handles_every_tail_length() {
+        // The tail-block padding path is only exercised for inputs that aren't a multiple of
+        // 8 bytes; walk every remainder so that none of them panics (e.g. by slicing out of
+        // bounds). The hash values themselves are not checked here.
+        let seed = [9u8; 16];
+        for len in 0..=16 {
+            let data: Vec<u8> = (0..len).collect();
+            let _ = SipHashFn::hash(&data, seed);
+        }
+    }
+
+    #[test]
+    fn random_seed_is_not_hardcoded() {
+        // Not a statistical proof of randomness, but catches the obvious regression of a
+        // fixed/constant seed.
+        let seeds: Vec<_> = (0..8).map(|_| random_seed()).collect();
+        assert!(seeds.windows(2).any(|w| w[0] != w[1]));
+    }
+
+    struct SipConfig;
+
+    impl crate::Config for SipConfig {
+        type Key = u64;
+        type Value = u32;
+
+        type RawKey = u64;
+        type RawValue = u32;
+
+        type H = SipHashFn;
+
+        fn encode_key(k: &u64) -> u64 {
+            *k
+        }
+
+        fn encode_value(v: &u32) -> u32 {
+            *v
+        }
+
+        fn decode_key(k: &u64) -> u64 {
+            *k
+        }
+
+        fn decode_value(v: &u32) -> u32 {
+            *v
+        }
+    }
+
+    #[test]
+    fn table_built_with_siphash_round_trips_through_serialization() {
+        let entries: Vec<(u64, u32)> = (0..100).map(|i| (i as u64, i as u32 * 11)).collect();
+
+        let mut builder = crate::HashTableBuilder::<SipConfig>::with_capacity(entries.len(), 0.7);
+        for (k, v) in &entries {
+            builder.insert(k, v);
+        }
+
+        let mut serialized = Vec::new();
+        builder.serialize(&mut serialized).unwrap();
+
+        // Reopening the table must use the same random seed the builder picked -- if the
+        // seed weren't persisted in the header, a freshly (and differently) seeded hash
+        // would send every lookup to the wrong slot.
+        let table = crate::HashTable::<SipConfig>::from_serialized(&serialized).unwrap();
+        for (k, v) in &entries {
+            assert_eq!(table.get(k), Some(*v));
+        }
+    }
+}